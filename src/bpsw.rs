@@ -0,0 +1,300 @@
+//! Baillie-PSW probable-prime test, extending [`IsPrime`] to `u128`.
+//!
+//! The deterministic Miller-Rabin witness sets in `impl_is_prime!` only cover integers up to 64
+//! bits. Baillie-PSW combines a base-2 strong Fermat (Miller-Rabin) test with a strong Lucas test
+//! using Selfridge's parameters; no composite has ever been found that passes both, so it gives a
+//! reliable answer for `u128` without a witness table.
+
+use crate::montgomery::{mul_wide_u128, wide_mod_u128, MontgomeryModulo};
+use crate::{IsPrime, SMALL_PRIMES_MEMO};
+
+impl IsPrime for u128 {
+    /// Determine whether `p` is prime using the Baillie-PSW probable-prime test.
+    ///
+    /// No composite number is known to pass this test, though (unlike the Miller-Rabin test used
+    /// for integers up to 64 bits) it is not proven that none exists.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use primality_test::IsPrime;
+    ///
+    /// assert!(998244353u128.is_prime());
+    /// assert!(170141183460469231731687303715884105727u128.is_prime());
+    /// assert!(!(1u128 << 100).is_prime());
+    /// ```
+    fn is_prime(&self) -> bool {
+        let p = *self;
+
+        if p < 2 {
+            return false;
+        }
+        if p & 1 == 0 {
+            return p == 2;
+        }
+        if p < SMALL_PRIMES_MEMO.len() as u128 {
+            return SMALL_PRIMES_MEMO.is_prime(p as usize);
+        }
+        for q in SMALL_PRIMES_MEMO.into_iter().skip(1) {
+            let q = q as u128;
+            if q * q > p {
+                break;
+            }
+            if p.is_multiple_of(q) {
+                return false;
+            }
+        }
+        if let Ok(p) = u64::try_from(p) {
+            return p.is_prime();
+        }
+
+        strong_fermat_base2(p) && strong_lucas_probable_prime(p)
+    }
+}
+
+/// Base-2 strong Fermat (Miller-Rabin) probable-prime test.
+fn strong_fermat_base2(p: u128) -> bool {
+    let mont = MontgomeryModulo::<u128>::new(p);
+
+    let s = (p - 1).trailing_zeros();
+    let t = (p - 1) >> s;
+
+    let a = mont.convert(2);
+    let mut at = mont.pow(a, t);
+    if at == mont.r || at == p - mont.r {
+        return true;
+    }
+
+    for _ in 1..s {
+        at = mont.multiply(at, at);
+        if at == p - mont.r {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Strong Lucas probable-prime test using Selfridge's Method A parameters (`P = 1`,
+/// `Q = (1 - D) / 4`).
+fn strong_lucas_probable_prime(n: u128) -> bool {
+    let Some(d) = selfridge_d(n) else {
+        return false;
+    };
+    let q = (1 - d) / 4;
+
+    let mont = MontgomeryModulo::<u128>::new(n);
+    let q_m = signed_to_montgomery(q, n, &mont);
+    let one = mont.r; // Montgomery form of `1`.
+
+    // n + 1 = e * 2^s with e odd.
+    let s = (n + 1).trailing_zeros();
+    let e = (n + 1) >> s;
+
+    let (u, mut v, mut qk) = lucas_uv(e, one, d, q_m, n, &mont);
+    if u == 0 {
+        return true;
+    }
+
+    for _ in 0..s {
+        if v == 0 {
+            return true;
+        }
+        v = mont.sub(mont.multiply(v, v), mont.double(qk));
+        qk = mont.multiply(qk, qk);
+    }
+
+    false
+}
+
+/// Compute `(U_e, V_e, Q^e) mod n` for the Lucas sequence with parameters `P = 1` and `Q`, via
+/// binary doubling. `u`, `v` and `qk` are kept in Montgomery form throughout; only `d` (needed to
+/// scale `U` by the plain integer `D` on odd steps) is a plain signed integer.
+fn lucas_uv(
+    e: u128,
+    one: u128,
+    d: i128,
+    q_m: u128,
+    n: u128,
+    mont: &MontgomeryModulo<u128>,
+) -> (u128, u128, u128) {
+    let bits = u128::BITS - e.leading_zeros();
+
+    let mut u = one; // U_1 = 1
+    let mut v = one; // V_1 = P = 1
+    let mut qk = q_m; // Q^1
+
+    for i in (0..bits - 1).rev() {
+        // Double: k -> 2k.
+        let u2 = mont.multiply(u, v);
+        let v2 = mont.sub(mont.multiply(v, v), mont.double(qk));
+        qk = mont.multiply(qk, qk);
+        u = u2;
+        v = v2;
+
+        if (e >> i) & 1 == 1 {
+            // Increment: k -> k + 1, specialized for P = 1:
+            // U_{k+1} = (U_k + V_k) / 2, V_{k+1} = (D*U_k + V_k) / 2.
+            let du = scalar_mulmod(d, u, n, mont);
+            let (new_u, new_v) = (mont.div2(mont.add(u, v)), mont.div2(mont.add(du, v)));
+            u = new_u;
+            v = new_v;
+            qk = mont.multiply(qk, q_m);
+        }
+    }
+
+    (u, v, qk)
+}
+
+/// Select the first `D` in `5, -7, 9, -11, 13, ...` with Jacobi symbol `(D|n) == -1`.
+///
+/// Returns `None` if `n` is a perfect square, in which case no such `D` exists and `n` must be
+/// composite.
+fn selfridge_d(n: u128) -> Option<i128> {
+    if is_perfect_square(n) {
+        return None;
+    }
+
+    let mut d: i128 = 5;
+    loop {
+        if jacobi(d, n) == -1 {
+            return Some(d);
+        }
+        d = if d > 0 { -(d + 2) } else { -d + 2 };
+    }
+}
+
+/// Jacobi symbol `(a|n)` for odd `n > 0`.
+fn jacobi(a: i128, n: u128) -> i32 {
+    let mut a = signed_mod(a, n);
+    let mut n = n;
+    let mut result = 1;
+
+    while a != 0 {
+        while a & 1 == 0 {
+            a >>= 1;
+            let r = n % 8;
+            if r == 3 || r == 5 {
+                result = -result;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// Reduce `a` into `[0, n)`.
+fn signed_mod(a: i128, n: u128) -> u128 {
+    if a >= 0 {
+        a as u128 % n
+    } else {
+        let mag = (-a) as u128 % n;
+        if mag == 0 {
+            0
+        } else {
+            n - mag
+        }
+    }
+}
+
+/// Convert a (possibly negative) plain integer into Montgomery form modulo `n`.
+fn signed_to_montgomery(a: i128, n: u128, mont: &MontgomeryModulo<u128>) -> u128 {
+    mont.convert(signed_mod(a, n))
+}
+
+/// Multiply the Montgomery-form value `v` by the plain (possibly negative) integer `d`, modulo
+/// `n`, keeping the result in Montgomery form.
+fn scalar_mulmod(d: i128, v: u128, n: u128, mont: &MontgomeryModulo<u128>) -> u128 {
+    let (hi, lo) = mul_wide_u128(d.unsigned_abs(), v);
+    let mag = wide_mod_u128(hi, lo, n);
+    if d < 0 {
+        mont.neg(mag)
+    } else {
+        mag
+    }
+}
+
+/// Floor of the integer square root of `n`.
+fn isqrt(n: u128) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    let mut x = 1u128 << (u128::BITS - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+    x
+}
+
+fn is_perfect_square(n: u128) -> bool {
+    let r = isqrt(n);
+    r * r == n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jacobi_matches_known_values() {
+        assert_eq!(jacobi(5, 21), 1);
+        assert_eq!(jacobi(-7, 21), 0);
+        assert_eq!(jacobi(2, 9), 1);
+        assert_eq!(jacobi(1001, 9907), -1);
+        assert_eq!(jacobi(19, 45), 1);
+        assert_eq!(jacobi(8, 21), -1);
+    }
+
+    #[test]
+    fn isqrt_matches_brute_force() {
+        for n in 0..5000u128 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+        assert!(is_perfect_square(0));
+        assert!(is_perfect_square(1));
+        assert!(is_perfect_square(1u128 << 100));
+        assert!(!is_perfect_square((1u128 << 100) + 1));
+    }
+
+    #[test]
+    fn matches_u64_is_prime_on_overlap() {
+        #[rustfmt::skip]
+        const PRIME: &[u64] = &[
+            2, 3, 5, 7, 11, 13, 998244353, 1000000007, 67280421310721,
+            999999999999999989,
+        ];
+        #[rustfmt::skip]
+        const COMPOSITE: &[u64] = &[
+            1, 4, 57, 561, 1105, 41041, 4759123141, 585226005592931977,
+        ];
+
+        for &p in PRIME {
+            assert!((p as u128).is_prime(), "{p} should be prime");
+        }
+        for &p in COMPOSITE {
+            assert!(!(p as u128).is_prime(), "{p} should be composite");
+        }
+    }
+
+    #[test]
+    fn large_known_primes() {
+        // Mersenne prime 2^127 - 1.
+        assert!((u128::MAX >> 1).is_prime());
+        // A 128-bit composite with two large prime factors.
+        let p: u128 = (u64::MAX as u128 - 58) * (u64::MAX as u128 - 82);
+        assert!(!p.is_prime());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::montgomery::Montgomery;
+use crate::montgomery::MontgomeryModulo;
 
 /// Determine if `p` is prime or not.
 ///
@@ -22,7 +22,7 @@ pub const fn is_prime(p: u64) -> bool {
         return p == 2;
     }
 
-    let mont = Montgomery::<u64>::new(p);
+    let mont = MontgomeryModulo::<u64>::new(p);
 
     let s = (p - 1).trailing_zeros();
     let t = (p - 1) >> s;
@@ -0,0 +1,202 @@
+//! Full-range `u64` integer factorization using Brent's variant of Pollard's rho.
+//!
+//! [`LinearSieve::factors`](crate::LinearSieve::factors) can only factor values below its
+//! compile-time `LEN`, so it cannot be used to factor an arbitrary `u64`. The functions in this
+//! module have no such limitation: they combine the crate's deterministic [`IsPrime`] test with
+//! Pollard's rho to find nontrivial factors of composite numbers.
+
+use crate::montgomery::MontgomeryModulo;
+use crate::IsPrime;
+
+/// Factorize `n` into its prime factors (with multiplicity), sorted ascending.
+///
+/// Returns an empty `Vec` if `n < 2`.
+///
+/// # Examples
+/// ```rust
+/// use primality_test::factorize;
+///
+/// assert_eq!(factorize(360), vec![2, 2, 2, 3, 3, 5]);
+/// assert_eq!(factorize(998244353), vec![998244353]);
+/// assert!(factorize(1).is_empty());
+/// ```
+pub fn factorize(n: u64) -> Vec<u64> {
+    let mut factors = vec![];
+    if n < 2 {
+        return factors;
+    }
+
+    let mut n = n;
+    while n & 1 == 0 {
+        factors.push(2);
+        n /= 2;
+    }
+    factorize_odd(n, &mut factors);
+
+    factors.sort_unstable();
+    factors
+}
+
+/// Factorize `n` into deduplicated prime factors with multiplicities, sorted ascending by the
+/// prime.
+///
+/// Returns an empty `Vec` if `n < 2`.
+///
+/// # Examples
+/// ```rust
+/// use primality_test::prime_factors;
+///
+/// assert_eq!(prime_factors(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// assert_eq!(prime_factors(998244353), vec![(998244353, 1)]);
+/// assert!(prime_factors(1).is_empty());
+/// ```
+pub fn prime_factors(n: u64) -> Vec<(u64, u32)> {
+    let mut res: Vec<(u64, u32)> = vec![];
+    for p in factorize(n) {
+        match res.last_mut() {
+            Some((last, count)) if *last == p => *count += 1,
+            _ => res.push((p, 1)),
+        }
+    }
+    res
+}
+
+/// Recursively split the odd value `n` into primes, appending them to `out`.
+fn factorize_odd(n: u64, out: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if n.is_prime() {
+        out.push(n);
+        return;
+    }
+
+    let d = pollard_rho(n);
+    factorize_odd(d, out);
+    factorize_odd(n / d, out);
+}
+
+/// Find a nontrivial factor of the odd composite `n` using Brent's variant of Pollard's rho.
+fn pollard_rho(n: u64) -> u64 {
+    let mont = MontgomeryModulo::<u64>::new(n);
+    let mut c = 1;
+    loop {
+        if let Some(d) = brent(n, &mont, c) {
+            return d;
+        }
+        c += 1;
+    }
+}
+
+/// One attempt of Brent's variant of Pollard's rho with the step function `f(x) = x*x + c (mod n)`.
+///
+/// `x`, `y` and the accumulated product are all kept in Montgomery form: since `gcd(R, n) == 1`,
+/// `gcd(v * R mod n, n) == gcd(v, n)`, so the final `gcd` against the batched product is valid
+/// without ever converting back out of Montgomery form.
+///
+/// Returns `None` if this choice of `c` failed to split `n`, so the caller should retry with
+/// `c + 1`.
+fn brent(n: u64, mont: &MontgomeryModulo<u64>, c: u64) -> Option<u64> {
+    const BATCH: u64 = 128;
+
+    let c = mont.convert(c % n);
+    let f = |x: u64| mont.add(mont.multiply(x, x), c);
+
+    let mut y = mont.convert(2 % n);
+    let mut x = y;
+    let mut ys = y;
+    let mut q = mont.r;
+    let mut g = 1;
+    let mut r = 1;
+
+    while g == 1 {
+        x = y;
+        for _ in 0..r {
+            y = f(y);
+        }
+
+        let mut k = 0;
+        while k < r && g == 1 {
+            ys = y;
+            let batch = BATCH.min(r - k);
+            for _ in 0..batch {
+                y = f(y);
+                let d = mont.sub(x, y);
+                // A zero difference means `x` and `y` have collided exactly; multiplying it in
+                // would just zero out `q`, so take the (trivial) gcd against `n` directly and
+                // let the step-by-step fallback below pin down the real collision point.
+                if d == 0 {
+                    g = n;
+                    break;
+                }
+                q = mont.multiply(q, d);
+            }
+            if g == 1 {
+                g = gcd(q, n);
+            }
+            k += batch;
+        }
+
+        r *= 2;
+    }
+
+    if g == n {
+        // The batched gcd collapsed to `n`; back off and take gcds step by step from the last
+        // checkpoint to pin down exactly where the cycle collided.
+        loop {
+            ys = f(ys);
+            g = gcd(mont.sub(x, ys), n);
+            if g != 1 {
+                break;
+            }
+        }
+    }
+
+    (g != n).then_some(g)
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        a %= b;
+        std::mem::swap(&mut a, &mut b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factorize_test() {
+        assert!(factorize(0).is_empty());
+        assert!(factorize(1).is_empty());
+        assert_eq!(factorize(2), vec![2]);
+        assert_eq!(factorize(4), vec![2, 2]);
+        assert_eq!(factorize(360), vec![2, 2, 2, 3, 3, 5]);
+        assert_eq!(factorize(998244353), vec![998244353]);
+        assert_eq!(factorize(999999999999999989), vec![999999999999999989]);
+
+        // 1122004669633 = 611557 * 1834669, both prime.
+        assert_eq!(factorize(1122004669633), vec![611557, 1834669]);
+
+        for n in 2..2000u64 {
+            let factors = factorize(n);
+            assert!(factors.iter().all(|p| p.is_prime()));
+            assert_eq!(factors.into_iter().product::<u64>(), n);
+        }
+    }
+
+    #[test]
+    fn prime_factors_test() {
+        assert!(prime_factors(1).is_empty());
+        assert_eq!(prime_factors(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(prime_factors(998244353), vec![(998244353, 1)]);
+
+        for n in 2..2000u64 {
+            let pf = prime_factors(n);
+            let rebuilt = pf.iter().fold(1u64, |acc, &(p, e)| acc * p.pow(e));
+            assert_eq!(rebuilt, n);
+        }
+    }
+}
@@ -6,13 +6,20 @@
 //!
 //! [Deterministic variants of the Miller-Rabin primality test](https://miller-rabin.appspot.com/)
 
+mod bpsw;
+mod factorize;
 mod montgomery;
+mod prime_nav;
+mod segmented_sieve;
 mod sieve;
 
-use montgomery::Montgomery;
+pub use factorize::{factorize, prime_factors};
+pub use montgomery::MontgomeryModulo;
+pub use prime_nav::{next_prime, nth_prime, prev_prime};
+pub use segmented_sieve::{primes, primes_up_to};
 pub use sieve::LinearSieve;
 
-const SMALL_PRIMES_MEMO: LinearSieve<255> = LinearSieve::new();
+pub(crate) const SMALL_PRIMES_MEMO: LinearSieve<255> = LinearSieve::new();
 
 pub trait IsPrime {
     fn is_prime(&self) -> bool;
@@ -48,7 +55,7 @@ macro_rules! impl_is_prime {
                     return SMALL_PRIMES_MEMO.is_prime(p as usize);
                 }
 
-                let mont = Montgomery::<$witness_ty>::new(p);
+                let mont = MontgomeryModulo::<$witness_ty>::new(p);
 
                 let s = (p - 1).trailing_zeros();
                 let t = (p - 1) >> s;
@@ -1,4 +1,26 @@
-pub(crate) struct Montgomery<T> {
+/// Fast modular arithmetic backed by Montgomery reduction.
+///
+/// `MontgomeryModulo` precomputes everything needed to do repeated multiplication, exponentiation
+/// and division modulo a fixed, odd `modulo` without ever performing a hardware division in the
+/// hot path. It underlies this crate's own primality tests, and is exposed so that downstream
+/// users doing modular exponentiation, NTT, or CRT can reuse it instead of reimplementing REDC.
+///
+/// All values passed to and returned from its methods are in Montgomery form (`value * R mod
+/// modulo` for the internal `R`), except the argument of [`convert`](Self::convert), the result of
+/// [`reduce`](Self::reduce), the exponent of [`pow`](Self::pow) and the modulus given to
+/// [`new`](Self::new); use [`convert`](Self::convert) to move a plain value into that form and
+/// [`reduce`](Self::reduce) to move it back out.
+///
+/// # Examples
+/// ```rust
+/// use primality_test::MontgomeryModulo;
+///
+/// let mont = MontgomeryModulo::<u64>::new(998244353);
+/// let a = mont.convert(123456789);
+/// let b = mont.convert(987654321);
+/// assert_eq!(mont.reduce(mont.multiply(a, b)), 123456789 * 987654321 % 998244353);
+/// ```
+pub struct MontgomeryModulo<T> {
     pub(crate) modulo: T,
     pub(crate) modulo_inv: T,
     pub(crate) r: T,
@@ -7,13 +29,14 @@ pub(crate) struct Montgomery<T> {
 
 macro_rules! impl_primitive_montgomery {
     ( $t:ty, $expand:ty ) => {
-        impl Montgomery<$t> {
+        impl MontgomeryModulo<$t> {
             // t <- MR(T) = floor(T/R) - floor((TN' mod R)*N/R)
             //  if t < 0 then return t + N else return t
             //      T := a (0 <= T < NR)
             //      N := MOD
             //      N':= MOD_INV    NN' = 1 (mod R)
             //      R := R
+            /// Convert `val` out of Montgomery form, i.e. compute `val * R^-1 mod modulo`.
             #[allow(unused)]
             pub const fn reduce(&self, val: $t) -> $t {
                 let (t, f) = (((val.wrapping_mul(self.modulo_inv) as $expand)
@@ -23,6 +46,7 @@ macro_rules! impl_primitive_montgomery {
                 t.wrapping_add(self.modulo * f as $t)
             }
 
+            /// Multiply two values in Montgomery form.
             pub const fn multiply(&self, lhs: $t, rhs: $t) -> $t {
                 let a = lhs as $expand * rhs as $expand;
                 let (t, f) = ((a >> <$t>::BITS) as $t).overflowing_sub(
@@ -33,10 +57,55 @@ macro_rules! impl_primitive_montgomery {
                 t.wrapping_add(self.modulo * f as $t)
             }
 
+            /// Add two values in Montgomery form.
+            pub const fn add(&self, lhs: $t, rhs: $t) -> $t {
+                let s = lhs as $expand + rhs as $expand;
+                (if s >= self.modulo as $expand {
+                    s - self.modulo as $expand
+                } else {
+                    s
+                }) as $t
+            }
+
+            /// Subtract `rhs` from `lhs`, both in Montgomery form.
+            pub const fn sub(&self, lhs: $t, rhs: $t) -> $t {
+                if lhs >= rhs {
+                    lhs - rhs
+                } else {
+                    self.modulo - (rhs - lhs)
+                }
+            }
+
+            /// Negate a value in Montgomery form.
+            pub const fn neg(&self, val: $t) -> $t {
+                if val == 0 {
+                    0
+                } else {
+                    self.modulo - val
+                }
+            }
+
+            /// Double a value in Montgomery form.
+            pub const fn double(&self, val: $t) -> $t {
+                self.add(val, val)
+            }
+
+            /// Halve a value in Montgomery form, using the `(modulo + 1) / 2` trick for the odd
+            /// `modulo`.
+            pub const fn div2(&self, val: $t) -> $t {
+                if val & 1 == 0 {
+                    val >> 1
+                } else {
+                    (val >> 1) + (self.modulo >> 1) + 1
+                }
+            }
+
+            /// Convert `val` into Montgomery form, i.e. compute `val * R mod modulo`.
             pub const fn convert(&self, val: $t) -> $t {
                 self.multiply(val, self.r2)
             }
 
+            /// Raise a value in Montgomery form to the power `exp` by repeated squaring.
             pub const fn pow(&self, val: $t, mut exp: $t) -> $t {
                 let (mut res, mut val) = (self.r, val);
                 while exp > 0 {
@@ -49,6 +118,15 @@ macro_rules! impl_primitive_montgomery {
                 res
             }
 
+            /// Compute the modular inverse of a value in Montgomery form via Fermat's little
+            /// theorem.
+            ///
+            /// Only correct when `modulo` is prime.
+            pub const fn inverse(&self, val: $t) -> $t {
+                self.pow(val, self.modulo - 2)
+            }
+
+            /// Build a `MontgomeryModulo` for the given odd `modulo`.
             pub const fn new(modulo: $t) -> Self {
                 let r = (((1 as $expand) << <$t>::BITS) % modulo as $expand) as $t;
                 let r2 = ((modulo as $expand).wrapping_neg() % modulo as $expand) as $t;
@@ -74,3 +152,210 @@ impl_primitive_montgomery!(u8, u16);
 impl_primitive_montgomery!(u16, u32);
 impl_primitive_montgomery!(u32, u64);
 impl_primitive_montgomery!(u64, u128);
+
+// `u128` has no built-in double-width integer to borrow for `$expand`, so its REDC is spelled out
+// by hand: 128x128 products are formed as an explicit (hi, lo) pair of `u128`s, and that pair is
+// folded back modulo `N` with the usual bit-at-a-time binary long division.
+
+/// Compute `a * b` as a `(hi, lo)` pair of `u128`s, i.e. `hi * 2^128 + lo == a * b`.
+pub(crate) const fn mul_wide_u128(a: u128, b: u128) -> (u128, u128) {
+    let (al, ah) = (a as u64 as u128, (a >> 64) as u64 as u128);
+    let (bl, bh) = (b as u64 as u128, (b >> 64) as u64 as u128);
+
+    let ll = al * bl;
+    let lh = al * bh;
+    let hl = ah * bl;
+    let hh = ah * bh;
+
+    let (mid, mid_overflow) = lh.overflowing_add(hl);
+    let (lo, lo_overflow) = ll.overflowing_add(mid << 64);
+    let hi = hh
+        .wrapping_add(mid >> 64)
+        .wrapping_add(if mid_overflow { 1 << 64 } else { 0 })
+        .wrapping_add(lo_overflow as u128);
+
+    (hi, lo)
+}
+
+/// Reduce the 256-bit value `hi * 2^128 + lo` modulo `m`, one bit at a time.
+pub(crate) const fn wide_mod_u128(hi: u128, lo: u128, m: u128) -> u128 {
+    const fn fold_in(mut r: u128, word: u128, m: u128) -> u128 {
+        let mut i = 128;
+        while i > 0 {
+            i -= 1;
+            let bit = (word >> i) & 1;
+            let carry = r >> 127;
+            let shifted = (r << 1) | bit;
+            r = if carry == 1 {
+                shifted.wrapping_sub(m)
+            } else if shifted >= m {
+                shifted - m
+            } else {
+                shifted
+            };
+        }
+        r
+    }
+
+    fold_in(fold_in(0, hi, m), lo, m)
+}
+
+impl MontgomeryModulo<u128> {
+    /// Convert `val` out of Montgomery form, i.e. compute `val * R^-1 mod modulo`.
+    #[allow(unused)]
+    pub const fn reduce(&self, val: u128) -> u128 {
+        let m = val.wrapping_mul(self.modulo_inv);
+        let (mhi, _) = mul_wide_u128(m, self.modulo);
+        let (t, f) = mhi.overflowing_neg();
+        t.wrapping_add(self.modulo * f as u128)
+    }
+
+    /// Multiply two values in Montgomery form.
+    pub const fn multiply(&self, lhs: u128, rhs: u128) -> u128 {
+        let (hi, lo) = mul_wide_u128(lhs, rhs);
+        let m = lo.wrapping_mul(self.modulo_inv);
+        let (mhi, _) = mul_wide_u128(m, self.modulo);
+        let (t, f) = hi.overflowing_sub(mhi);
+        t.wrapping_add(self.modulo * f as u128)
+    }
+
+    /// Add two values in Montgomery form.
+    pub const fn add(&self, lhs: u128, rhs: u128) -> u128 {
+        let (s, overflow) = lhs.overflowing_add(rhs);
+        if overflow {
+            s.wrapping_sub(self.modulo)
+        } else if s >= self.modulo {
+            s - self.modulo
+        } else {
+            s
+        }
+    }
+
+    /// Subtract `rhs` from `lhs`, both in Montgomery form.
+    pub const fn sub(&self, lhs: u128, rhs: u128) -> u128 {
+        if lhs >= rhs {
+            lhs - rhs
+        } else {
+            self.modulo - (rhs - lhs)
+        }
+    }
+
+    /// Negate a value in Montgomery form.
+    pub const fn neg(&self, val: u128) -> u128 {
+        if val == 0 {
+            0
+        } else {
+            self.modulo - val
+        }
+    }
+
+    /// Double a value in Montgomery form.
+    pub const fn double(&self, val: u128) -> u128 {
+        self.add(val, val)
+    }
+
+    /// Halve a value in Montgomery form, using the `(modulo + 1) / 2` trick for the odd `modulo`.
+    pub const fn div2(&self, val: u128) -> u128 {
+        if val & 1 == 0 {
+            val >> 1
+        } else {
+            (val >> 1) + (self.modulo >> 1) + 1
+        }
+    }
+
+    /// Convert `val` into Montgomery form, i.e. compute `val * R mod modulo`.
+    pub const fn convert(&self, val: u128) -> u128 {
+        self.multiply(val, self.r2)
+    }
+
+    /// Raise a value in Montgomery form to the power `exp` by repeated squaring.
+    pub const fn pow(&self, val: u128, mut exp: u128) -> u128 {
+        let (mut res, mut val) = (self.r, val);
+        while exp > 0 {
+            if exp & 1 != 0 {
+                res = self.multiply(res, val);
+            }
+            val = self.multiply(val, val);
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// Compute the modular inverse of a value in Montgomery form via Fermat's little theorem.
+    ///
+    /// Only correct when `modulo` is prime.
+    pub const fn inverse(&self, val: u128) -> u128 {
+        self.pow(val, self.modulo - 2)
+    }
+
+    /// Build a `MontgomeryModulo` for the given odd `modulo`.
+    pub const fn new(modulo: u128) -> Self {
+        // `r = 2^128 mod modulo`, obtained by reducing the 256-bit value `1 * 2^128 + 0`.
+        let r = wide_mod_u128(1, 0, modulo);
+        // `r2 = r^2 mod modulo = 2^256 mod modulo`.
+        let r2 = {
+            let (hi, lo) = mul_wide_u128(r, r);
+            wide_mod_u128(hi, lo, modulo)
+        };
+        let modulo_inv = {
+            let mut inv = modulo;
+            while modulo.wrapping_mul(inv) != 1 {
+                inv = inv.wrapping_mul(2u128.wrapping_sub(modulo.wrapping_mul(inv)));
+            }
+            inv
+        };
+        Self {
+            modulo,
+            modulo_inv,
+            r,
+            r2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_sub_neg_double_div2() {
+        let mont = MontgomeryModulo::<u64>::new(998244353);
+        let a = mont.convert(123456789);
+        let b = mont.convert(987654321);
+
+        assert_eq!(mont.reduce(mont.add(a, b)), (123456789 + 987654321) % 998244353);
+        assert_eq!(
+            mont.reduce(mont.sub(a, b)),
+            ((123456789i64 - 987654321).rem_euclid(998244353)) as u64
+        );
+        assert_eq!(mont.reduce(mont.neg(a)), 998244353 - 123456789);
+        assert_eq!(mont.reduce(mont.double(a)), 123456789 * 2);
+        assert_eq!(mont.reduce(mont.div2(mont.double(a))), 123456789);
+    }
+
+    #[test]
+    fn inverse_is_multiplicative_inverse() {
+        let mont = MontgomeryModulo::<u64>::new(998244353);
+        let a = mont.convert(123456789);
+        let inv = mont.inverse(a);
+        assert_eq!(mont.reduce(mont.multiply(a, inv)), 1);
+    }
+
+    #[test]
+    fn u128_matches_u64_arithmetic() {
+        let modulo: u128 = 998244353;
+        let mont128 = MontgomeryModulo::<u128>::new(modulo);
+        let mont64 = MontgomeryModulo::<u64>::new(modulo as u64);
+
+        let a = mont128.convert(123456789);
+        let b = mont128.convert(987654321);
+        let a64 = mont64.convert(123456789);
+        let b64 = mont64.convert(987654321);
+
+        assert_eq!(mont128.reduce(mont128.add(a, b)), mont64.reduce(mont64.add(a64, b64)) as u128);
+        assert_eq!(mont128.reduce(mont128.sub(a, b)), mont64.reduce(mont64.sub(a64, b64)) as u128);
+        assert_eq!(mont128.reduce(mont128.multiply(a, b)), mont64.reduce(mont64.multiply(a64, b64)) as u128);
+        assert_eq!(mont128.reduce(mont128.div2(a)), mont64.reduce(mont64.div2(a64)) as u128);
+        assert_eq!(mont128.reduce(mont128.inverse(a)), mont64.reduce(mont64.inverse(a64)) as u128);
+    }
+}
@@ -0,0 +1,174 @@
+//! Prime range-navigation helpers (`next_prime`, `prev_prime`, `nth_prime`) built directly on
+//! [`IsPrime`], for callers (cryptographic parameter selection, hash table sizing) who need the
+//! prime nearest to a value rather than a full enumeration.
+//!
+//! Candidates are generated by stepping over the residues mod `2 * 3 * 5 = 30` that are coprime to
+//! `2`, `3` and `5`, skipping the 22 out of every 30 integers that are trivially composite, rather
+//! than sieving a range.
+
+use crate::IsPrime;
+
+/// Residues mod 30 coprime to `2 * 3 * 5`, ascending.
+const WHEEL: [u64; 8] = [1, 7, 11, 13, 17, 19, 23, 29];
+
+/// Step `(base, idx)` forward to the next wheel position, `base` being a multiple of 30 and `idx`
+/// indexing into [`WHEEL`]. Returns `None` on overflow past `u64::MAX`.
+fn next_wheel(base: u64, idx: usize) -> Option<(u64, usize)> {
+    if idx + 1 < WHEEL.len() {
+        Some((base, idx + 1))
+    } else {
+        Some((base.checked_add(30)?, 0))
+    }
+}
+
+/// Step `(base, idx)` backward to the previous wheel position. Returns `None` on underflow below
+/// `0`.
+fn prev_wheel(base: u64, idx: usize) -> Option<(u64, usize)> {
+    if idx > 0 {
+        Some((base, idx - 1))
+    } else {
+        Some((base.checked_sub(30)?, WHEEL.len() - 1))
+    }
+}
+
+/// Return the smallest prime strictly greater than `n`, or `None` if it would overflow `u64`.
+///
+/// # Examples
+/// ```rust
+/// use primality_test::next_prime;
+///
+/// assert_eq!(next_prime(0), Some(2));
+/// assert_eq!(next_prime(10), Some(11));
+/// assert_eq!(next_prime(28), Some(29));
+/// assert_eq!(next_prime(u64::MAX), None);
+/// ```
+pub fn next_prime(n: u64) -> Option<u64> {
+    for p in [2, 3, 5] {
+        if n < p {
+            return Some(p);
+        }
+    }
+
+    let base = n - n % 30;
+    let rem = n % 30;
+    let mut pos = match WHEEL.iter().position(|&r| r > rem) {
+        Some(idx) => (base, idx),
+        None => (base.checked_add(30)?, 0),
+    };
+
+    loop {
+        let (base, idx) = pos;
+        let candidate = base.checked_add(WHEEL[idx])?;
+        if candidate.is_prime() {
+            return Some(candidate);
+        }
+        pos = next_wheel(base, idx)?;
+    }
+}
+
+/// Return the largest prime strictly less than `n`, or `None` if no such prime exists (`n <= 2`).
+///
+/// # Examples
+/// ```rust
+/// use primality_test::prev_prime;
+///
+/// assert_eq!(prev_prime(3), Some(2));
+/// assert_eq!(prev_prime(11), Some(7));
+/// assert_eq!(prev_prime(30), Some(29));
+/// assert_eq!(prev_prime(2), None);
+/// ```
+pub fn prev_prime(n: u64) -> Option<u64> {
+    if n <= 2 {
+        return None;
+    }
+    if n <= 3 {
+        return Some(2);
+    }
+    if n <= 5 {
+        return Some(3);
+    }
+    if n <= 7 {
+        return Some(5);
+    }
+
+    let base = n - n % 30;
+    let rem = n % 30;
+    let mut pos = match WHEEL.iter().rposition(|&r| r < rem) {
+        Some(idx) => (base, idx),
+        None => (base.checked_sub(30)?, WHEEL.len() - 1),
+    };
+
+    loop {
+        let (base, idx) = pos;
+        let candidate = base + WHEEL[idx];
+        if candidate.is_prime() {
+            return Some(candidate);
+        }
+        pos = prev_wheel(base, idx)?;
+    }
+}
+
+/// Return the `k`-th prime (1-indexed, so `nth_prime(1) == Some(2)`), or `None` if `k == 0` or the
+/// search would overflow `u64`.
+///
+/// # Examples
+/// ```rust
+/// use primality_test::nth_prime;
+///
+/// assert_eq!(nth_prime(1), Some(2));
+/// assert_eq!(nth_prime(10), Some(29));
+/// assert_eq!(nth_prime(0), None);
+/// ```
+pub fn nth_prime(k: u64) -> Option<u64> {
+    if k == 0 {
+        return None;
+    }
+
+    let mut p = 1;
+    for _ in 0..k {
+        p = next_prime(p)?;
+    }
+    Some(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_prime_matches_brute_force() {
+        let mut expected = 1;
+        for n in 0..2000u64 {
+            while expected <= n || !expected.is_prime() {
+                expected += 1;
+            }
+            assert_eq!(next_prime(n), Some(expected), "next_prime({n})");
+        }
+    }
+
+    #[test]
+    fn prev_prime_matches_brute_force() {
+        assert_eq!(prev_prime(0), None);
+        assert_eq!(prev_prime(1), None);
+        assert_eq!(prev_prime(2), None);
+
+        for n in 3..2000u64 {
+            let expected = (2..n).rev().find(|p| p.is_prime());
+            assert_eq!(prev_prime(n), expected, "prev_prime({n})");
+        }
+    }
+
+    #[test]
+    fn next_prime_overflow() {
+        assert_eq!(next_prime(u64::MAX), None);
+        assert_eq!(next_prime(u64::MAX - 1), None);
+    }
+
+    #[test]
+    fn nth_prime_matches_brute_force() {
+        let mut primes = (2..10000u64).filter(|p| p.is_prime());
+        for k in 1..=100u64 {
+            assert_eq!(nth_prime(k), primes.next(), "nth_prime({k})");
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Segmented sieve of Eratosthenes for enumerating primes over large ranges in bounded memory.
+//!
+//! [`LinearSieve<LEN>`](crate::LinearSieve) stack-allocates `[usize; LEN]` and is fixed at compile
+//! time, so its own docs warn that it cannot be used for large `LEN` without risking a stack
+//! overflow. The functions in this module instead sieve one heap-allocated block `[lo, hi)` at a
+//! time, crossing off multiples of the base primes up to `sqrt(hi)` (found via the crate's
+//! deterministic [`IsPrime`] test), then yield the survivors before advancing to the next block.
+
+use crate::IsPrime;
+
+const BLOCK_SIZE: u64 = 1 << 16;
+
+/// Iterate over the primes in `[2, limit)`, ascending.
+///
+/// # Examples
+/// ```rust
+/// use primality_test::primes_up_to;
+///
+/// assert_eq!(primes_up_to(30).collect::<Vec<_>>(), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+/// assert!(primes_up_to(2).next().is_none());
+/// ```
+pub fn primes_up_to(limit: u64) -> impl Iterator<Item = u64> {
+    Primes::new().take_while(move |&p| p < limit)
+}
+
+/// Iterate over all primes, ascending and without bound.
+///
+/// # Examples
+/// ```rust
+/// use primality_test::primes;
+///
+/// assert_eq!(primes().take(10).collect::<Vec<_>>(), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+/// ```
+pub fn primes() -> impl Iterator<Item = u64> {
+    Primes::new()
+}
+
+/// Segmented-sieve iterator: holds the current block `[lo, lo + block.len())` and which of its
+/// positions have survived sieving, plus the base primes (up to `sqrt` of the current block's
+/// upper bound) used to sieve it.
+struct Primes {
+    lo: u64,
+    block: Vec<bool>,
+    pos: usize,
+    /// Base primes found so far, up to `base_primes_limit`. Grown incrementally as later blocks
+    /// need larger base primes, rather than recomputed from scratch every block.
+    base_primes: Vec<u64>,
+    base_primes_limit: u64,
+}
+
+impl Primes {
+    fn new() -> Self {
+        let mut primes = Self {
+            lo: 0,
+            block: Vec::new(),
+            pos: 0,
+            base_primes: Vec::new(),
+            base_primes_limit: 1,
+        };
+        primes.sieve_block(0);
+        primes
+    }
+
+    /// Extend `base_primes` with any primes in `(base_primes_limit, limit]`.
+    fn extend_base_primes(&mut self, limit: u64) {
+        if limit <= self.base_primes_limit {
+            return;
+        }
+        self.base_primes
+            .extend((self.base_primes_limit + 1..=limit).filter(|p| p.is_prime()));
+        self.base_primes_limit = limit;
+    }
+
+    /// Sieve the block `[lo, lo + BLOCK_SIZE)` (clamped to `u64::MAX`), replacing the current one.
+    fn sieve_block(&mut self, lo: u64) {
+        let hi = lo.saturating_add(BLOCK_SIZE);
+        let len = (hi - lo) as usize;
+
+        self.block.clear();
+        self.block.resize(len, true);
+        if lo == 0 {
+            for composite in [0, 1] {
+                if let Some(slot) = self.block.get_mut(composite) {
+                    *slot = false;
+                }
+            }
+        }
+
+        self.extend_base_primes(isqrt(hi.saturating_sub(1)));
+        for &p in &self.base_primes {
+            // Multiples below `p*p` are already crossed off by smaller primes.
+            let start = if p.saturating_mul(p) > lo {
+                p * p
+            } else {
+                lo.div_ceil(p) * p
+            };
+
+            let mut m = start;
+            while m < hi {
+                self.block[(m - lo) as usize] = false;
+                m += p;
+            }
+        }
+
+        self.lo = lo;
+        self.pos = 0;
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            while self.pos < self.block.len() {
+                let idx = self.pos;
+                self.pos += 1;
+                if self.block[idx] {
+                    return Some(self.lo + idx as u64);
+                }
+            }
+
+            let next_lo = self.lo + self.block.len() as u64;
+            if next_lo <= self.lo {
+                // Reached and exhausted the block ending at `u64::MAX`.
+                return None;
+            }
+            self.sieve_block(next_lo);
+        }
+    }
+}
+
+/// Floor of the integer square root of `n`.
+fn isqrt(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let mut x = 1u64 << (u64::BITS - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primes_up_to_matches_linear_sieve() {
+        use crate::LinearSieve;
+
+        const LEN: usize = 10000;
+        const S: LinearSieve<LEN> = LinearSieve::new();
+
+        let expected = S
+            .into_iter()
+            .map(|p| p as u64)
+            .filter(|&p| p < LEN as u64)
+            .collect::<Vec<_>>();
+        assert_eq!(primes_up_to(LEN as u64).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn primes_up_to_crosses_several_blocks() {
+        let limit = BLOCK_SIZE * 3 + 17;
+        let found = primes_up_to(limit).collect::<Vec<_>>();
+
+        assert!(found.iter().all(|&p| p.is_prime()));
+        assert_eq!(found.len(), found.iter().collect::<std::collections::HashSet<_>>().len());
+
+        let mut prev = 0;
+        for &p in &found {
+            assert!((prev + 1..p).all(|n| !n.is_prime()));
+            prev = p;
+        }
+    }
+
+    #[test]
+    fn primes_is_unbounded_and_matches_primes_up_to() {
+        let from_unbounded = primes().take(500).collect::<Vec<_>>();
+        let from_bounded = primes_up_to(from_unbounded.last().copied().unwrap() + 1)
+            .collect::<Vec<_>>();
+        assert_eq!(from_unbounded, from_bounded);
+    }
+}